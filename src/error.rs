@@ -0,0 +1,24 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+/// Errors that can be returned by the MessageBoard program.
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MessageBoardError {
+    /// The paying account does not hold enough lamports, or the paying
+    /// member's dues balance is too low, to cover the requested amount.
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+    /// The board's membership balance for this address is not positive.
+    #[error("Membership is not active")]
+    MembershipInactive,
+    /// The transaction bundles more than one balance-mutating instruction
+    /// (`PayDues`/`Withdraw`) targeting this program.
+    #[error("Transaction bundles multiple balance-mutating instructions")]
+    ConflictingBalanceInstruction,
+}
+
+impl From<MessageBoardError> for ProgramError {
+    fn from(e: MessageBoardError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,126 @@
+//! Fuzzing harness for `process_instruction`, enabled via the `fuzzing` feature.
+//!
+//! `fuzz_process_instruction` is the entrypoint a fuzzer driver (e.g. honggfuzz
+//! or cargo-fuzz) should call with raw bytes. Those bytes are bincode-decoded
+//! into a `FuzzInput`, which is deliberately biased toward the shapes most
+//! likely to trip `MessageBoardInstruction::unpack` and the account checks in
+//! `create_board`/`post_message`: truncated buffers, invalid UTF-8 tails, and
+//! token accounts whose owner/mint/amount don't satisfy the program's checks.
+//! Every code path must return a `ProgramResult` error rather than panicking
+//! or exhibiting UB.
+
+use bumpalo::Bump;
+use lazy_static::lazy_static;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use solana_program::{account_info::AccountInfo, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::Account as TokenAccount;
+
+use crate::{id, process_instruction};
+
+lazy_static! {
+    static ref PROGRAM_ID: Pubkey = id();
+    static ref TOKEN_PROGRAM_ID: Pubkey = spl_token::id();
+}
+
+/// A fuzzer-controlled description of one `process_instruction` call.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FuzzInput {
+    /// Instruction variant byte; left unconstrained so variants beyond the
+    /// five the program currently understands get exercised too.
+    pub variant: u8,
+    /// The 32 bytes that would normally hold a `Pubkey` in the instruction
+    /// payload. Truncated by `prefix_len` below to stress the
+    /// `rest.get(..32)` check in `unpack`.
+    pub token_or_seed_bytes: Vec<u8>,
+    /// Raw bytes appended after the pubkey-sized prefix; may be invalid UTF-8.
+    pub tail_bytes: Vec<u8>,
+    /// How many of `token_or_seed_bytes` to actually include, to produce
+    /// instruction data shorter than the 32 bytes `unpack` expects.
+    pub prefix_len: u8,
+    pub token_account_owner: [u8; 32],
+    pub token_account_mint: [u8; 32],
+    pub token_account_amount: u64,
+    pub num_accounts: u8,
+    pub seed: u64,
+}
+
+fn instruction_bytes(input: &FuzzInput) -> Vec<u8> {
+    let mut data = vec![input.variant];
+    let prefix_len = (input.prefix_len as usize).min(input.token_or_seed_bytes.len());
+    data.extend_from_slice(&input.token_or_seed_bytes[..prefix_len]);
+    data.extend_from_slice(&input.tail_bytes);
+    data
+}
+
+fn packed_token_account(input: &FuzzInput) -> [u8; spl_token::state::Account::LEN] {
+    let account = TokenAccount {
+        mint: Pubkey::new_from_array(input.token_account_mint),
+        owner: Pubkey::new_from_array(input.token_account_owner),
+        amount: input.token_account_amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..TokenAccount::default()
+    };
+    let mut packed = [0u8; spl_token::state::Account::LEN];
+    TokenAccount::pack(account, &mut packed).expect("fuzz token account always fits its own LEN");
+    packed
+}
+
+/// Builds a slab of synthetic `AccountInfo`s for a single fuzz iteration,
+/// backed by memory carved out of `arena` so the borrows can outlive this
+/// function call without needing a `Box`/`Rc`.
+fn build_fuzz_accounts<'a>(arena: &'a Bump, rng: &mut StdRng, input: &FuzzInput) -> Vec<AccountInfo<'a>> {
+    let num_accounts = input.num_accounts.max(1) as usize;
+    let mut accounts = Vec::with_capacity(num_accounts);
+
+    // `post_message` reads its token account at index 5 and `create_board` at
+    // index 3; in both, the token program account comes right after it.
+    let token_account_index = if input.variant == 1 { 5 } else { 3 };
+
+    for i in 0..num_accounts {
+        let key = arena.alloc(if i == token_account_index + 1 { *TOKEN_PROGRAM_ID } else { Pubkey::new_unique() });
+        let lamports = arena.alloc(rng.gen_range(0..=1_000_000_000u64));
+        let owner = arena.alloc(if i == token_account_index { *TOKEN_PROGRAM_ID } else { *PROGRAM_ID });
+
+        // One of the accounts masquerades as the token account the program
+        // inspects with `spl_token::state::Account::unpack`.
+        let data: &'a mut [u8] = if i == token_account_index {
+            arena.alloc_slice_copy(&packed_token_account(input))
+        } else {
+            let len = rng.gen_range(0..256);
+            arena.alloc_slice_fill_with(len, |_| rng.gen())
+        };
+
+        accounts.push(AccountInfo::new(
+            key,
+            i == 0,
+            true,
+            lamports,
+            data,
+            owner,
+            false,
+            0,
+        ));
+    }
+
+    accounts
+}
+
+/// Entrypoint for a fuzzer driver. Decodes `data` into a `FuzzInput` and runs
+/// one `process_instruction` call, panicking only if the program itself
+/// panics or invokes UB — ordinary `ProgramResult` errors are expected and
+/// swallowed.
+pub fn fuzz_process_instruction(data: &[u8]) {
+    let input: FuzzInput = match bincode::deserialize(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    let arena = Bump::new();
+    let mut rng = StdRng::seed_from_u64(input.seed);
+
+    let instruction_data = instruction_bytes(&input);
+    let accounts = build_fuzz_accounts(&arena, &mut rng, &input);
+
+    let _ = process_instruction(&PROGRAM_ID, &accounts, &instruction_data);
+}
@@ -1,17 +1,38 @@
+// solana-program's `entrypoint!` macro checks cfgs this version of the
+// compiler doesn't know about; allow that rather than the unrelated
+// `unexpected_cfgs` noise it produces on every build.
+#![allow(unexpected_cfgs)]
+
 use solana_program::{
-    account_info::AccountInfo,
+    account_info::{next_account_info, AccountInfo},
     entrypoint,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     msg,
     program_error::ProgramError,
-    sysvar::{rent::Rent, Sysvar},
-    program_pack::{Pack, IsInitialized},
+    sysvar::{
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        rent::Rent,
+        Sysvar,
+    },
+    program_pack::Pack,
     system_instruction,
 };
 use spl_token::state::Account as TokenAccount;
 use borsh::{BorshDeserialize, BorshSerialize};
 
+mod error;
+use error::MessageBoardError;
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+
+// The deployed address of this program.
+solana_program::declare_id!("5P1hDH43EWQUfh6aUiqbRLDbJo1Gau8aNd9T8VbNoVd");
+
+// Lamports charged to a poster for each message, paid straight to the board owner.
+const POSTING_FEE_LAMPORTS: u64 = 5_000;
+
 // Define the state struct for our message board info
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct MessageBoardInfo {
@@ -19,6 +40,212 @@ pub struct MessageBoardInfo {
     pub owner: Pubkey,
     pub token: Pubkey,
     pub url: String,
+    pub bump: u8,
+}
+
+impl MessageBoardInfo {
+    /// Exact size of a `MessageBoardInfo` once Borsh-serialized, given the
+    /// length of its `url` field. `std::mem::size_of` cannot be used here
+    /// since it reports the in-memory layout of the `String` handle rather
+    /// than its length-prefixed serialized form.
+    pub fn serialized_len(url: &str) -> usize {
+        1 // is_initialized
+            + 32 // owner
+            + 32 // token
+            + 4 + url.len() // url: u32 length prefix + bytes
+            + 1 // bump
+    }
+}
+
+// Seed prefix for deriving a board's PDA from its token mint.
+pub const BOARD_SEED_PREFIX: &[u8] = b"board";
+
+/// Loads and validates a `MessageBoardInfo` from `account`, so that
+/// instructions operating on an existing board can't be tricked into
+/// reading attacker-controlled data living under a different owning
+/// program. Checks that the account is owned by this program, that it has
+/// been initialized, and that its stored `token`/`bump` still derive the
+/// account's own address, so a forged `token` can't be smuggled in. The
+/// stored `owner` is not checked beyond the account being owned by this program.
+pub fn load_board(account: &AccountInfo, program_id: &Pubkey) -> Result<MessageBoardInfo, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let board_info = MessageBoardInfo::try_from_slice(&account.data.borrow())?;
+    if !board_info.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let expected_key = Pubkey::create_program_address(
+        &[BOARD_SEED_PREFIX, board_info.token.as_ref(), &[board_info.bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+    if expected_key != *account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(board_info)
+}
+
+// Seed prefix for deriving a member's registration PDA from a board and member.
+pub const REGISTRATION_SEED_PREFIX: &[u8] = b"registration";
+
+// A member's paid-membership standing on a board. `balance` is the member's
+// dues balance, credited by `PayDues` and debited by `Withdraw`; posting
+// requires it to be positive but does not debit it.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Registration {
+    pub is_initialized: bool,
+    pub member: Pubkey,
+    pub board: Pubkey,
+    pub balance: i64,
+    pub bump: u8,
+}
+
+impl Registration {
+    /// Exact Borsh-serialized size of a `Registration`; unlike
+    /// `MessageBoardInfo` it has no variable-length fields, but we size it
+    /// explicitly anyway rather than trust `std::mem::size_of`'s layout.
+    pub fn serialized_len() -> usize {
+        1 // is_initialized
+            + 32 // member
+            + 32 // board
+            + 8 // balance
+            + 1 // bump
+    }
+}
+
+/// Loads and validates a `Registration` from `account`, mirroring
+/// `load_board`'s owner/initialization/PDA-consistency checks.
+pub fn load_registration(account: &AccountInfo, program_id: &Pubkey) -> Result<Registration, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let registration = Registration::try_from_slice(&account.data.borrow())?;
+    if !registration.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let expected_key = Pubkey::create_program_address(
+        &[
+            REGISTRATION_SEED_PREFIX,
+            registration.board.as_ref(),
+            registration.member.as_ref(),
+            &[registration.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+    if expected_key != *account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(registration)
+}
+
+/// Rejects the current transaction if it bundles more than one
+/// `PayDues`/`Withdraw` instruction targeting this program, closing the
+/// reentrancy/double-spend hole those instructions would otherwise open if a
+/// single message could stack multiple balance mutations against the same
+/// registration account.
+fn assert_no_conflicting_balance_instruction(
+    instructions_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+
+    let mut index = 0usize;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        let is_balance_mutation = matches!(instruction.data.first(), Some(&3) | Some(&4));
+        if index != current_index && instruction.program_id == *program_id && is_balance_mutation {
+            return Err(MessageBoardError::ConflictingBalanceInstruction.into());
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+// A single message posted to a board.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Message {
+    pub poster: Pubkey,
+    pub body: String,
+}
+
+// The growable list of messages backing a board.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MessageList {
+    pub is_initialized: bool,
+    pub board: Pubkey,
+    pub bump: u8,
+    pub messages: Vec<Message>,
+}
+
+impl MessageList {
+    /// Exact Borsh-serialized size of a freshly created, empty `MessageList`.
+    pub fn serialized_len() -> usize {
+        1 // is_initialized
+            + 32 // board
+            + 1 // bump
+            + 4 // messages: empty Vec's u32 length prefix
+    }
+}
+
+// Seed prefix for deriving a board's messages-list PDA from its board account.
+pub const MESSAGES_SEED_PREFIX: &[u8] = b"messages";
+
+/// Loads `messages_account`'s `MessageList`, verifying its key is the PDA
+/// derived from `board_account` so a list meant for one board can't be
+/// smuggled in as the messages account for another. A still-empty account
+/// (all-zero bytes, as a freshly allocated one is) is treated as an
+/// as-yet-uninitialized list for `board_account` rather than an error, since
+/// this is the first time it's being written to.
+fn load_or_init_message_list(
+    messages_account: &AccountInfo,
+    board_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<MessageList, ProgramError> {
+    if messages_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if messages_account.data.borrow().iter().all(|&b| b == 0) {
+        let (expected_key, bump) = Pubkey::find_program_address(
+            &[MESSAGES_SEED_PREFIX, board_account.key.as_ref()],
+            program_id,
+        );
+        if expected_key != *messages_account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        return Ok(MessageList {
+            is_initialized: true,
+            board: *board_account.key,
+            bump,
+            messages: Vec::new(),
+        });
+    }
+
+    let message_list = MessageList::try_from_slice(&messages_account.data.borrow())?;
+    if !message_list.is_initialized || message_list.board != *board_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let expected_key = Pubkey::create_program_address(
+        &[MESSAGES_SEED_PREFIX, board_account.key.as_ref(), &[message_list.bump]],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+    if expected_key != *messages_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(message_list)
 }
 
 // Program entrypoint
@@ -30,6 +257,10 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    if program_id != &id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
     // Deserialize instruction data
     let instruction = MessageBoardInstruction::unpack(instruction_data)?;
 
@@ -37,6 +268,18 @@ pub fn process_instruction(
         MessageBoardInstruction::CreateBoard { token, url } => {
             create_board(program_id, accounts, token, url)
         }
+        MessageBoardInstruction::PostMessage { body } => {
+            post_message(program_id, accounts, body)
+        }
+        MessageBoardInstruction::RegisterMember { member } => {
+            register_member(program_id, accounts, member)
+        }
+        MessageBoardInstruction::PayDues { amount } => {
+            pay_dues(program_id, accounts, amount)
+        }
+        MessageBoardInstruction::Withdraw { amount } => {
+            withdraw(program_id, accounts, amount)
+        }
     }
 }
 
@@ -47,16 +290,56 @@ pub enum MessageBoardInstruction {
         token: Pubkey,
         url: String,
     },
+    PostMessage {
+        body: String,
+    },
+    RegisterMember {
+        member: Pubkey,
+    },
+    PayDues {
+        amount: u64,
+    },
+    Withdraw {
+        amount: u64,
+    },
 }
 
 impl MessageBoardInstruction {
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
         let (&variant, rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
         Ok(match variant {
-            0 => Self::CreateBoard {
-                token: Pubkey::new(&rest[..32]),
-                url: String::from_utf8(rest[32..].to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?,
+            0 => {
+                let token_bytes = rest.get(..32).ok_or(ProgramError::InvalidInstructionData)?;
+                Self::CreateBoard {
+                    token: Pubkey::try_from(token_bytes).map_err(|_| ProgramError::InvalidInstructionData)?,
+                    url: String::from_utf8(rest[32..].to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?,
+                }
+            }
+            1 => Self::PostMessage {
+                body: String::from_utf8(rest.to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?,
             },
+            2 => {
+                let member_bytes = rest.get(..32).ok_or(ProgramError::InvalidInstructionData)?;
+                Self::RegisterMember {
+                    member: Pubkey::try_from(member_bytes).map_err(|_| ProgramError::InvalidInstructionData)?,
+                }
+            }
+            3 => {
+                let amount_bytes = rest.get(..8).ok_or(ProgramError::InvalidInstructionData)?;
+                Self::PayDues {
+                    amount: u64::from_le_bytes(
+                        amount_bytes.try_into().map_err(|_| ProgramError::InvalidInstructionData)?,
+                    ),
+                }
+            }
+            4 => {
+                let amount_bytes = rest.get(..8).ok_or(ProgramError::InvalidInstructionData)?;
+                Self::Withdraw {
+                    amount: u64::from_le_bytes(
+                        amount_bytes.try_into().map_err(|_| ProgramError::InvalidInstructionData)?,
+                    ),
+                }
+            }
             _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
@@ -71,6 +354,7 @@ fn create_board(
     let account_info_iter = &mut accounts.iter();
     let sender = next_account_info(account_info_iter)?;
     let board_account = next_account_info(account_info_iter)?;
+    let messages_account = next_account_info(account_info_iter)?;
     let token_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
@@ -90,9 +374,18 @@ fn create_board(
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Create the board account
+    // Derive the board's PDA from its token mint so there can only ever be one
+    // board per token and the program itself authorizes the account's creation.
+    let (board_pda, bump) =
+        Pubkey::find_program_address(&[BOARD_SEED_PREFIX, token.as_ref()], program_id);
+    if board_pda != *board_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Create the board account, sized to the Borsh-serialized length rather
+    // than the in-memory layout of MessageBoardInfo
     let rent = Rent::from_account_info(rent_sysvar_account)?;
-    let space = std::mem::size_of::<MessageBoardInfo>();
+    let space = MessageBoardInfo::serialized_len(&url);
     let lamports = rent.minimum_balance(space);
 
     // Create account instruction
@@ -104,23 +397,311 @@ fn create_board(
         program_id,
     );
 
-    // Execute create account instruction
+    // Execute create account instruction, signing with the PDA's own seeds
     solana_program::program::invoke_signed(
         &create_account_ix,
         &[sender.clone(), board_account.clone(), system_program.clone()],
-        &[],
+        &[&[BOARD_SEED_PREFIX, token.as_ref(), &[bump]]],
     )?;
 
     // Initialize the board account data
-    let mut board_info = MessageBoardInfo {
+    let board_info = MessageBoardInfo {
         is_initialized: true,
         owner: *sender.key,
         token,
         url,
+        bump,
     };
 
     board_info.serialize(&mut &mut board_account.data.borrow_mut()[..])?;
 
+    // Derive and create the board's messages-list PDA alongside it, so every
+    // board always has a messages account only this program could have
+    // signed for, rather than trusting a client-supplied one.
+    let (messages_pda, messages_bump) = Pubkey::find_program_address(
+        &[MESSAGES_SEED_PREFIX, board_account.key.as_ref()],
+        program_id,
+    );
+    if messages_pda != *messages_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let messages_space = MessageList::serialized_len();
+    let messages_lamports = rent.minimum_balance(messages_space);
+
+    let create_messages_account_ix = system_instruction::create_account(
+        sender.key,
+        messages_account.key,
+        messages_lamports,
+        messages_space as u64,
+        program_id,
+    );
+
+    solana_program::program::invoke_signed(
+        &create_messages_account_ix,
+        &[sender.clone(), messages_account.clone(), system_program.clone()],
+        &[&[MESSAGES_SEED_PREFIX, board_account.key.as_ref(), &[messages_bump]]],
+    )?;
+
+    let message_list = MessageList {
+        is_initialized: true,
+        board: *board_account.key,
+        bump: messages_bump,
+        messages: Vec::new(),
+    };
+    message_list.serialize(&mut &mut messages_account.data.borrow_mut()[..])?;
+
     msg!("Message board created for token: {:?}", token);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Grows `account` to `new_len` bytes, topping up its lamports to the new
+/// rent-exempt minimum (paid by `payer`, via a System Program CPI since
+/// `payer` isn't owned by this program) before reallocating so it never dips
+/// below rent-exemption mid-grow.
+fn realloc_to_fit<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &Rent,
+    new_len: usize,
+) -> ProgramResult {
+    let new_minimum_balance = rent.minimum_balance(new_len);
+    let lamports_diff = new_minimum_balance.saturating_sub(account.lamports());
+    if lamports_diff > 0 {
+        if payer.lamports() < lamports_diff {
+            return Err(MessageBoardError::InsufficientFunds.into());
+        }
+        solana_program::program::invoke(
+            &system_instruction::transfer(payer.key, account.key, lamports_diff),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(new_len, false)?;
+    Ok(())
+}
+
+fn post_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    body: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let poster = next_account_info(account_info_iter)?;
+    let board_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let messages_account = next_account_info(account_info_iter)?;
+    let registration_account = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+
+    // Check if the poster is the signer
+    if !poster.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let board_info = load_board(board_account, program_id)?;
+    if owner_account.key != &board_info.owner {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Validate that the poster holds a nonzero balance of the board's token
+    if token_account.owner != token_program.key {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let token_account_data = TokenAccount::unpack(&token_account.data.borrow())?;
+    if token_account_data.owner != *poster.key || token_account_data.mint != board_info.token || token_account_data.amount == 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Posting is additionally gated on paid membership: the poster must be
+    // registered for this board with a positive dues balance.
+    let registration = load_registration(registration_account, program_id)?;
+    if registration.member != *poster.key || registration.board != *board_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if registration.balance <= 0 {
+        return Err(MessageBoardError::MembershipInactive.into());
+    }
+
+    // Charge the posting fee straight out of the poster's wallet and into the
+    // board owner's via a System Program CPI; poster isn't owned by this
+    // program, so its lamports can't be debited by mutating the account directly.
+    if poster.lamports() < POSTING_FEE_LAMPORTS {
+        return Err(MessageBoardError::InsufficientFunds.into());
+    }
+    solana_program::program::invoke(
+        &system_instruction::transfer(poster.key, owner_account.key, POSTING_FEE_LAMPORTS),
+        &[poster.clone(), owner_account.clone(), system_program.clone()],
+    )?;
+
+    // Append the message to the board's message list, growing the account
+    // to fit if the new list no longer fits in its current allocation.
+    // `load_or_init_message_list` verifies `messages_account` is the PDA
+    // derived from `board_account`, so a list meant for one board can't be
+    // swapped in for another's.
+    let mut message_list = load_or_init_message_list(messages_account, board_account, program_id)?;
+    message_list.messages.push(Message {
+        poster: *poster.key,
+        body,
+    });
+
+    let new_data = message_list.try_to_vec()?;
+    if new_data.len() > messages_account.data_len() {
+        let rent = Rent::from_account_info(rent_sysvar_account)?;
+        realloc_to_fit(messages_account, poster, system_program, &rent, new_data.len())?;
+    }
+    messages_account.data.borrow_mut()[..new_data.len()].copy_from_slice(&new_data);
+
+    msg!("Message posted to board owned by: {:?}", board_info.owner);
+    Ok(())
+}
+
+fn register_member(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    member: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let board_account = next_account_info(account_info_iter)?;
+    let registration_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The board must already exist for a membership to attach to it.
+    load_board(board_account, program_id)?;
+
+    let (registration_pda, bump) = Pubkey::find_program_address(
+        &[REGISTRATION_SEED_PREFIX, board_account.key.as_ref(), member.as_ref()],
+        program_id,
+    );
+    if registration_pda != *registration_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent = Rent::from_account_info(rent_sysvar_account)?;
+    let space = Registration::serialized_len();
+    let lamports = rent.minimum_balance(space);
+
+    let create_account_ix = system_instruction::create_account(
+        payer.key,
+        registration_account.key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    solana_program::program::invoke_signed(
+        &create_account_ix,
+        &[payer.clone(), registration_account.clone(), system_program.clone()],
+        &[&[
+            REGISTRATION_SEED_PREFIX,
+            board_account.key.as_ref(),
+            member.as_ref(),
+            &[bump],
+        ]],
+    )?;
+
+    let registration = Registration {
+        is_initialized: true,
+        member,
+        board: *board_account.key,
+        balance: 0,
+        bump,
+    };
+    registration.serialize(&mut &mut registration_account.data.borrow_mut()[..])?;
+
+    msg!("Registered member {:?} on board {:?}", member, board_account.key);
+    Ok(())
+}
+
+fn pay_dues(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let member = next_account_info(account_info_iter)?;
+    let registration_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+
+    if !member.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_no_conflicting_balance_instruction(instructions_sysvar, program_id)?;
+
+    let mut registration = load_registration(registration_account, program_id)?;
+    if registration.member != *member.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Move the dues lamports from the member into the registration account via
+    // a System Program CPI (member isn't owned by this program, so its
+    // lamports can't be debited by mutating the account directly), and only
+    // then record the credit, so the two can never diverge.
+    if member.lamports() < amount {
+        return Err(MessageBoardError::InsufficientFunds.into());
+    }
+    solana_program::program::invoke(
+        &system_instruction::transfer(member.key, registration_account.key, amount),
+        &[member.clone(), registration_account.clone(), system_program.clone()],
+    )?;
+    let amount_i64 = i64::try_from(amount).map_err(|_| ProgramError::InvalidArgument)?;
+    registration.balance = registration
+        .balance
+        .checked_add(amount_i64)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    registration.serialize(&mut &mut registration_account.data.borrow_mut()[..])?;
+
+    msg!("Member {:?} paid {} lamports in dues", member.key, amount);
+    Ok(())
+}
+
+fn withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let member = next_account_info(account_info_iter)?;
+    let registration_account = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+
+    if !member.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_no_conflicting_balance_instruction(instructions_sysvar, program_id)?;
+
+    let mut registration = load_registration(registration_account, program_id)?;
+    if registration.member != *member.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Check both the tracked balance and the actual lamports *before*
+    // mutating either, so a transfer that can't be satisfied never silently
+    // no-ops while still crediting the member's balance.
+    let requested = i64::try_from(amount).map_err(|_| ProgramError::InvalidArgument)?;
+    if registration.balance < requested || registration_account.lamports() < amount {
+        return Err(MessageBoardError::InsufficientFunds.into());
+    }
+
+    **registration_account.try_borrow_mut_lamports()? -= amount;
+    **member.try_borrow_mut_lamports()? += amount;
+    registration.balance -= requested;
+
+    registration.serialize(&mut &mut registration_account.data.borrow_mut()[..])?;
+
+    msg!("Member {:?} withdrew {} lamports", member.key, amount);
+    Ok(())
+}
@@ -0,0 +1,291 @@
+//! Happy-path integration tests for the create/post/register/pay/withdraw
+//! flows, run against a real in-process `Bank` via `solana-program-test` so
+//! PDA derivation, the posting-fee CPI, the Borsh-sized realloc growth on
+//! `MessageList`, and the instructions-sysvar introspection guard are all
+//! exercised for real rather than only read.
+
+use apechan_solana::{
+    id, process_instruction, MessageBoardInfo, MessageList, Registration, BOARD_SEED_PREFIX,
+    MESSAGES_SEED_PREFIX, REGISTRATION_SEED_PREFIX,
+};
+use borsh::BorshDeserialize;
+use solana_program_test::{processor, tokio, ProgramTest};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    sysvar::{instructions, rent},
+    transaction::Transaction,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+fn create_board_ix(sender: &Pubkey, board: &Pubkey, messages: &Pubkey, token_account: &Pubkey, token: &Pubkey, url: &str) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(token.as_ref());
+    data.extend_from_slice(url.as_bytes());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*sender, true),
+            AccountMeta::new(*board, false),
+            AccountMeta::new(*messages, false),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data,
+    }
+}
+
+fn post_message_ix(
+    poster: &Pubkey,
+    board: &Pubkey,
+    owner: &Pubkey,
+    messages: &Pubkey,
+    registration: &Pubkey,
+    token_account: &Pubkey,
+    body: &str,
+) -> Instruction {
+    let mut data = vec![1u8];
+    data.extend_from_slice(body.as_bytes());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*poster, true),
+            AccountMeta::new_readonly(*board, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(*messages, false),
+            AccountMeta::new_readonly(*registration, false),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data,
+    }
+}
+
+fn register_member_ix(payer: &Pubkey, board: &Pubkey, registration: &Pubkey, member: &Pubkey) -> Instruction {
+    let mut data = vec![2u8];
+    data.extend_from_slice(member.as_ref());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*board, false),
+            AccountMeta::new(*registration, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+        ],
+        data,
+    }
+}
+
+fn pay_dues_ix(member: &Pubkey, registration: &Pubkey, amount: u64) -> Instruction {
+    let mut data = vec![3u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*member, true),
+            AccountMeta::new(*registration, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(instructions::id(), false),
+        ],
+        data,
+    }
+}
+
+fn withdraw_ix(member: &Pubkey, registration: &Pubkey, amount: u64) -> Instruction {
+    let mut data = vec![4u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*member, true),
+            AccountMeta::new(*registration, false),
+            AccountMeta::new_readonly(instructions::id(), false),
+        ],
+        data,
+    }
+}
+
+#[tokio::test]
+async fn create_post_register_pay_withdraw_happy_path() {
+    let program_test = ProgramTest::new("apechan_solana", id(), processor!(process_instruction));
+    let (mut banks_client, owner, recent_blockhash) = program_test.start().await;
+
+    let poster = Keypair::new();
+
+    let mint = Keypair::new();
+    let rent = banks_client.get_rent().await.unwrap();
+    let mint_rent = rent.minimum_balance(Mint::LEN);
+    let token_account_rent = rent.minimum_balance(TokenAccount::LEN);
+
+    let owner_token_account = Keypair::new();
+    let poster_token_account = Keypair::new();
+
+    let setup_tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::transfer(&owner.pubkey(), &poster.pubkey(), 1_000_000_000),
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &mint.pubkey(),
+                mint_rent,
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &owner.pubkey(),
+                None,
+                0,
+            )
+            .unwrap(),
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &owner_token_account.pubkey(),
+                token_account_rent,
+                TokenAccount::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &owner_token_account.pubkey(),
+                &mint.pubkey(),
+                &owner.pubkey(),
+            )
+            .unwrap(),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &owner_token_account.pubkey(),
+                &owner.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap(),
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &poster_token_account.pubkey(),
+                token_account_rent,
+                TokenAccount::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &poster_token_account.pubkey(),
+                &mint.pubkey(),
+                &poster.pubkey(),
+            )
+            .unwrap(),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &poster_token_account.pubkey(),
+                &owner.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap(),
+        ],
+        Some(&owner.pubkey()),
+        &[&owner, &mint, &owner_token_account, &poster_token_account],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(setup_tx).await.unwrap();
+
+    let url = "https://example.com/board";
+    let (board, _board_bump) =
+        Pubkey::find_program_address(&[BOARD_SEED_PREFIX, mint.pubkey().as_ref()], &id());
+    let (messages, _messages_bump) =
+        Pubkey::find_program_address(&[MESSAGES_SEED_PREFIX, board.as_ref()], &id());
+    let (registration, _registration_bump) = Pubkey::find_program_address(
+        &[REGISTRATION_SEED_PREFIX, board.as_ref(), poster.pubkey().as_ref()],
+        &id(),
+    );
+
+    let create_board_tx = Transaction::new_signed_with_payer(
+        &[create_board_ix(
+            &owner.pubkey(),
+            &board,
+            &messages,
+            &owner_token_account.pubkey(),
+            &mint.pubkey(),
+            url,
+        )],
+        Some(&owner.pubkey()),
+        &[&owner],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(create_board_tx).await.unwrap();
+
+    let board_account = banks_client.get_account(board).await.unwrap().unwrap();
+    let board_info = MessageBoardInfo::try_from_slice(&board_account.data).unwrap();
+    assert_eq!(board_info.owner, owner.pubkey());
+    assert_eq!(board_info.token, mint.pubkey());
+    assert_eq!(board_info.url, url);
+
+    let register_tx = Transaction::new_signed_with_payer(
+        &[register_member_ix(&poster.pubkey(), &board, &registration, &poster.pubkey())],
+        Some(&poster.pubkey()),
+        &[&poster],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(register_tx).await.unwrap();
+
+    let pay_dues_tx = Transaction::new_signed_with_payer(
+        &[pay_dues_ix(&poster.pubkey(), &registration, 10_000)],
+        Some(&poster.pubkey()),
+        &[&poster],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(pay_dues_tx).await.unwrap();
+
+    let registration_account = banks_client.get_account(registration).await.unwrap().unwrap();
+    let registration_data = Registration::try_from_slice(&registration_account.data).unwrap();
+    assert_eq!(registration_data.balance, 10_000);
+
+    let post_message_tx = Transaction::new_signed_with_payer(
+        &[post_message_ix(
+            &poster.pubkey(),
+            &board,
+            &owner.pubkey(),
+            &messages,
+            &registration,
+            &poster_token_account.pubkey(),
+            "hello, board",
+        )],
+        Some(&poster.pubkey()),
+        &[&poster],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(post_message_tx).await.unwrap();
+
+    let messages_account = banks_client.get_account(messages).await.unwrap().unwrap();
+    let message_list = MessageList::try_from_slice(&messages_account.data).unwrap();
+    assert_eq!(message_list.messages.len(), 1);
+    assert_eq!(message_list.messages[0].poster, poster.pubkey());
+    assert_eq!(message_list.messages[0].body, "hello, board");
+
+    let withdraw_tx = Transaction::new_signed_with_payer(
+        &[withdraw_ix(&poster.pubkey(), &registration, 4_000)],
+        Some(&poster.pubkey()),
+        &[&poster],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(withdraw_tx).await.unwrap();
+
+    let registration_account = banks_client.get_account(registration).await.unwrap().unwrap();
+    let registration_data = Registration::try_from_slice(&registration_account.data).unwrap();
+    assert_eq!(registration_data.balance, 6_000);
+}